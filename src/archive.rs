@@ -0,0 +1,155 @@
+//! Introspection of archive files (`.tar`, `.tar.gz`, `.zip`) so their contents can be folded
+//! into the walk's counts without extracting them to disk.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+/// One member stored inside an archive: whether it is a directory, its uncompressed size, and
+/// its path within the archive.
+pub type ArchiveMember = (bool, u64, String);
+
+/// An archive that can enumerate its members without being extracted, the same way a directory
+/// entry is enumerated during a regular walk.
+pub trait ArchiveReader {
+    /// List every member stored in the archive.
+    fn members(&self) -> io::Result<Vec<ArchiveMember>>;
+}
+
+struct TarReader {
+    path: std::path::PathBuf,
+    gzipped: bool,
+}
+
+impl ArchiveReader for TarReader {
+    fn members(&self) -> io::Result<Vec<ArchiveMember>> {
+        let file = File::open(&self.path)?;
+        let mut archive = if self.gzipped {
+            tar::Archive::new(Box::new(GzDecoder::new(file)) as Box<dyn io::Read>)
+        } else {
+            tar::Archive::new(Box::new(file) as Box<dyn io::Read>)
+        };
+
+        let mut members = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let is_dir = entry.header().entry_type().is_dir();
+            let name = entry.path()?.to_string_lossy().into_owned();
+            members.push((is_dir, entry.header().size()?, name));
+        }
+        Ok(members)
+    }
+}
+
+struct ZipReader {
+    path: std::path::PathBuf,
+}
+
+impl ArchiveReader for ZipReader {
+    fn members(&self) -> io::Result<Vec<ArchiveMember>> {
+        let file = File::open(&self.path)?;
+        let mut archive =
+            zip::ZipArchive::new(file).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let mut members = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let entry = archive
+                .by_index(i)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            members.push((entry.is_dir(), entry.size(), entry.name().to_string()));
+        }
+        Ok(members)
+    }
+}
+
+/// Return an archive reader for `path` if it has a recognized archive extension.
+pub fn open(path: &Path) -> Option<Box<dyn ArchiveReader>> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(Box::new(TarReader {
+            path: path.to_path_buf(),
+            gzipped: true,
+        }))
+    } else if name.ends_with(".tar") {
+        Some(Box::new(TarReader {
+            path: path.to_path_buf(),
+            gzipped: false,
+        }))
+    } else if name.ends_with(".zip") {
+        Some(Box::new(ZipReader {
+            path: path.to_path_buf(),
+        }))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("fcnt-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn open_recognizes_archive_extensions() {
+        assert!(open(Path::new("a.tar")).is_some());
+        assert!(open(Path::new("a.tar.gz")).is_some());
+        assert!(open(Path::new("a.tgz")).is_some());
+        assert!(open(Path::new("a.zip")).is_some());
+        assert!(open(Path::new("a.txt")).is_none());
+    }
+
+    #[test]
+    fn tar_reader_lists_members() {
+        let path = temp_path("sample.tar");
+        let file = File::create(&path).unwrap();
+        let mut builder = tar::Builder::new(file);
+        let data = b"hello world";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, "hello.txt", &data[..]).unwrap();
+        builder.finish().unwrap();
+        drop(builder);
+
+        let members = open(&path).unwrap().members().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(members, vec![(false, 11, "hello.txt".to_string())]);
+    }
+
+    #[test]
+    fn zip_reader_lists_members() {
+        let path = temp_path("sample.zip");
+        let file = File::create(&path).unwrap();
+        let mut writer = zip::ZipWriter::new(file);
+        writer
+            .start_file("hello.txt", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"hello world").unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let members = open(&path).unwrap().members().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(members, vec![(false, 11, "hello.txt".to_string())]);
+    }
+
+    #[test]
+    fn tar_reader_errors_on_a_truncated_archive() {
+        let path = temp_path("broken.tar");
+        std::fs::write(&path, b"not a tar file").unwrap();
+
+        let result = open(&path).unwrap().members();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use clap::{Parser, ValueEnum};
+use regex::Regex;
+
+/// The column the final report is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OrderBy {
+    /// Sort by directory name.
+    N,
+    /// Sort by file count.
+    F,
+    /// Sort by directory count.
+    D,
+    /// Sort by size.
+    S,
+    /// Sort by most recent modification time.
+    T,
+}
+
+/// Count the files and directories under one or more paths.
+#[derive(Debug, Parser)]
+#[command(name = "fcnt", version, about = "Count files and directories in a tree.")]
+pub struct CmdArgParser {
+    /// The directories to scan (defaults to the current directory).
+    pub directories: Vec<String>,
+
+    /// Include hidden files and directories (those whose name starts with '.'). Applies
+    /// whether or not `--gitignore` is set.
+    #[arg(short = 'a', long = "all")]
+    pub all_files: bool,
+
+    /// Also report the total size of each directory.
+    #[arg(short = 's', long = "size")]
+    pub with_size: bool,
+
+    /// Scan the given paths one after another on the main thread instead of across the shared
+    /// worker pool.
+    #[arg(short = 'n', long = "non-recursive")]
+    pub non_recursive: bool,
+
+    /// Only count files whose name matches this regular expression.
+    #[arg(short = 'e', long = "regex")]
+    pub regex: Option<String>,
+
+    /// Sort the report by this column.
+    #[arg(short = 'O', long = "order-by", value_enum)]
+    pub order_by: Option<OrderBy>,
+
+    /// Print every counted file's path as it is walked.
+    #[arg(short = 'v', long = "verbose")]
+    pub verbose: bool,
+
+    /// Number of worker threads to use for the parallel walk (defaults to the number of CPUs).
+    #[arg(short = 't', long = "threads")]
+    pub threads: Option<usize>,
+
+    /// Respect .gitignore/.ignore files and global git excludes while walking.
+    #[arg(short = 'g', long = "gitignore")]
+    pub gitignore: bool,
+
+    /// When `--gitignore` is set, still include hidden files instead of skipping them.
+    /// `--all` does the same whether or not `--gitignore` is set.
+    #[arg(long = "hidden")]
+    pub hidden: bool,
+
+    /// Open .tar/.tar.gz/.zip files and count the files and directories stored inside them.
+    #[arg(long = "archives")]
+    pub archives: bool,
+
+    /// Report allocated disk usage (blocks on disk) instead of apparent file size.
+    #[arg(short = 'd', long = "disk-usage")]
+    pub disk_usage: bool,
+
+    /// Also report each directory's most recent modification time.
+    #[arg(long = "mtime")]
+    pub mtime: bool,
+
+    /// Also print the N largest individual files across all scanned directories.
+    #[arg(long = "top")]
+    pub top: Option<usize>,
+
+    /// Group the walk results by file extension and print a file-count/size table instead.
+    #[arg(long = "by-ext")]
+    pub by_ext: bool,
+}
+
+impl CmdArgParser {
+    /// Return the directories to scan, defaulting to the current directory when none were given.
+    pub fn get_directories(&self) -> Vec<String> {
+        if self.directories.is_empty() {
+            vec![".".to_string()]
+        } else {
+            self.directories.clone()
+        }
+    }
+
+    /// Compile the `--regex` filter, if one was given.
+    pub fn get_regex(&self) -> Option<Regex> {
+        self.regex.as_deref().map(|pattern| {
+            Regex::new(pattern).unwrap_or_else(|err| panic!("invalid regex `{pattern}`: {err}"))
+        })
+    }
+
+    /// Resolve the number of threads to use for `parallel_walk`.
+    pub fn get_threads_num(&self) -> usize {
+        self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+    }
+}
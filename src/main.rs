@@ -1,42 +1,55 @@
+mod archive;
 mod cmdargs;
 mod output;
 mod walker;
 
+use std::cmp::Reverse;
+
 use clap::Parser;
 use cmdargs::{CmdArgParser, OrderBy};
-use walker::Counter;
+use walker::{Counter, WalkOptions};
 
 fn main() {
     // parse cmd-line args and get directories
     let args = CmdArgParser::parse();
-    let with_size = args.with_size || args.order_by == Some(OrderBy::S);
+    let with_size = args.with_size || args.disk_usage || args.order_by == Some(OrderBy::S);
 
     // walk all files
     let directories = args.get_directories();
     let filter = args.get_regex();
     let with_dir = filter.is_none();
+    let with_mtime = args.mtime || args.order_by == Some(OrderBy::T);
+    let opts = WalkOptions {
+        all_files: args.all_files,
+        with_size,
+        with_mtime,
+        filter,
+        verbose: args.verbose,
+        gitignore: args.gitignore,
+        hidden: args.hidden,
+        archives: args.archives,
+        disk_usage: args.disk_usage,
+    };
+    let mut top = args.top.map(walker::TopFiles::new);
+    let mut by_ext = args.by_ext.then(walker::ExtBreakdown::new);
     let mut counters = Vec::<Counter>::new();
     if args.non_recursive {
         for dirpath in directories {
-            if let Ok((_, counter)) = walker::walk(
-                &dirpath,
-                args.all_files,
-                with_size,
-                filter.clone(),
-                args.verbose,
-            ) {
+            if let Ok((_, counter)) = walker::walk(&dirpath, &opts, top.as_mut(), by_ext.as_mut()) {
                 counters.push(counter);
             };
         }
     } else {
-        counters = walker::parallel_walk(
+        let (walked, walked_top, walked_by_ext) = walker::parallel_walk(
             directories,
-            args.all_files,
-            with_size,
-            filter,
-            args.verbose,
+            opts,
             args.get_threads_num(),
+            args.top,
+            args.by_ext,
         );
+        counters = walked;
+        top = walked_top;
+        by_ext = walked_by_ext;
     }
 
     match args.order_by {
@@ -44,16 +57,27 @@ fn main() {
             counters.sort_by(|c1, c2| c1.dirpath.cmp(&c2.dirpath));
         }
         Some(OrderBy::F) => {
-            counters.sort_by(|c1, c2| c2.n_files.cmp(&c1.n_files));
+            counters.sort_by_key(|c| Reverse(c.n_files));
         }
         Some(OrderBy::D) => {
-            counters.sort_by(|c1, c2| c2.n_dirs.cmp(&c1.n_dirs));
+            counters.sort_by_key(|c| Reverse(c.n_dirs));
         }
         Some(OrderBy::S) => {
-            counters.sort_by(|c1, c2| c2.size().cmp(&c1.size()));
+            counters.sort_by_key(|c| Reverse(c.size()));
+        }
+        Some(OrderBy::T) => {
+            counters.sort_by_key(|c| Reverse(c.mtime()));
         }
         None => {}
     }
 
-    Counter::output(&counters, with_dir, with_size);
+    if let Some(by_ext) = by_ext {
+        output::output_by_ext(by_ext.into_entries(), args.order_by);
+    } else {
+        Counter::output(&counters, with_dir, with_size, with_mtime);
+    }
+
+    if let Some(top) = top {
+        output::output_top(&top.into_sorted_vec());
+    }
 }
@@ -0,0 +1,106 @@
+use std::cmp::Reverse;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use crate::cmdargs::OrderBy;
+use crate::walker::Counter;
+
+impl Counter {
+    /// Print the final report table to stdout.
+    pub fn output(counters: &[Counter], with_dir: bool, with_size: bool, with_mtime: bool) {
+        for counter in counters {
+            let mut line = format!("{:>10} files", counter.n_files);
+            if with_dir {
+                line.push_str(&format!("  {:>10} dirs", counter.n_dirs));
+            }
+            if with_size {
+                line.push_str(&format!("  {:>14} bytes", counter.size()));
+            }
+            if with_mtime {
+                line.push_str(&format!("  {:>12}", format_mtime(counter)));
+            }
+            line.push_str(&format!("  {}", counter.dirpath));
+            println!("{line}");
+        }
+    }
+}
+
+/// Render a `Counter`'s most recent modification time as seconds since the Unix epoch, or "-"
+/// when no file was found during the walk.
+fn format_mtime(counter: &Counter) -> String {
+    match counter.mtime() {
+        Some(mtime) => match mtime.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_secs().to_string(),
+            Err(_) => "-".to_string(),
+        },
+        None => "-".to_string(),
+    }
+}
+
+/// Print the `--top` report: the largest files found across all scanned directories, descending.
+pub fn output_top(files: &[(u64, PathBuf)]) {
+    println!("\nlargest files:");
+    for (size, path) in files {
+        println!("{:>14} bytes  {}", size, path.display());
+    }
+}
+
+/// Print the `--by-ext` report: one row per file extension, sorted by the active `OrderBy`.
+pub fn output_by_ext(mut entries: Vec<(String, u64, u64)>, order_by: Option<OrderBy>) {
+    match order_by {
+        Some(OrderBy::N) => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+        Some(OrderBy::F) => entries.sort_by_key(|e| Reverse(e.1)),
+        // `D` (dir count) and `T` (mtime) don't apply to an extension breakdown; fall back to size.
+        Some(OrderBy::S) | Some(OrderBy::D) | Some(OrderBy::T) | None => {
+            entries.sort_by_key(|e| Reverse(e.2))
+        }
+    }
+
+    for (ext, n_files, size) in entries {
+        println!("{:>10} files  {:>14} bytes  {ext}", n_files, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::walker::{self, WalkOptions};
+
+    #[test]
+    fn format_mtime_is_a_dash_when_no_file_was_walked() {
+        let counter = Counter::default();
+        assert_eq!(format_mtime(&counter), "-");
+    }
+
+    #[test]
+    fn format_mtime_renders_seconds_since_the_epoch_once_a_file_is_walked() {
+        let dir = std::env::temp_dir().join(format!("fcnt-test-format-mtime-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("f.txt"), b"x").unwrap();
+        let opts = WalkOptions {
+            all_files: false,
+            with_size: false,
+            with_mtime: true,
+            filter: None,
+            verbose: false,
+            gitignore: false,
+            hidden: false,
+            archives: false,
+            disk_usage: false,
+        };
+
+        let (_, counter) = walker::walk(dir.to_str().unwrap(), &opts, None, None).unwrap();
+        let expected = counter
+            .mtime()
+            .unwrap()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string();
+        assert_eq!(format_mtime(&counter), expected);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,723 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use ignore::{WalkBuilder, WalkState};
+use regex::Regex;
+
+use crate::archive;
+
+/// Shared knobs for a single walk, independent of how many directories are scanned.
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    pub all_files: bool,
+    pub with_size: bool,
+    /// Track each directory's most recent modification time.
+    pub with_mtime: bool,
+    pub filter: Option<Regex>,
+    pub verbose: bool,
+    /// Route the walk through the `ignore` crate so `.gitignore`/`.ignore`/global excludes
+    /// and the hidden-file convention are respected.
+    pub gitignore: bool,
+    /// When `gitignore` is set, include hidden files instead of skipping them. `all_files` has
+    /// the same effect and takes precedence regardless of which one is set.
+    pub hidden: bool,
+    /// Open `.tar`/`.tar.gz`/`.zip` files and fold the members they contain into the owning
+    /// directory's counts.
+    pub archives: bool,
+    /// Sum allocated disk usage (`st_blocks * 512` on Unix) instead of apparent file length.
+    pub disk_usage: bool,
+}
+
+/// Filesystem allocation unit assumed on platforms without `st_blocks`, used to round apparent
+/// file lengths up the same way `--disk-usage` does on Unix.
+#[cfg(not(unix))]
+const ALLOC_UNIT: u64 = 4096;
+
+/// Size to charge a file against the running total, honoring `--disk-usage`.
+#[cfg(unix)]
+fn file_size(metadata: &fs::Metadata, disk_usage: bool) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    if disk_usage {
+        metadata.blocks() * 512
+    } else {
+        metadata.len()
+    }
+}
+
+#[cfg(not(unix))]
+fn file_size(metadata: &fs::Metadata, disk_usage: bool) -> u64 {
+    let len = metadata.len();
+    if disk_usage {
+        len.div_ceil(ALLOC_UNIT) * ALLOC_UNIT
+    } else {
+        len
+    }
+}
+
+/// Accumulated file/directory counts (and optional size) for one scanned directory tree.
+#[derive(Debug, Default, Clone)]
+pub struct Counter {
+    pub dirpath: String,
+    pub n_files: u64,
+    pub n_dirs: u64,
+    size: u64,
+    mtime: Option<SystemTime>,
+}
+
+impl Counter {
+    fn new(dirpath: String) -> Self {
+        Counter {
+            dirpath,
+            ..Default::default()
+        }
+    }
+
+    /// Total size (in bytes) of every file counted in this directory's subtree.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The most recent modification time found anywhere in this directory's subtree.
+    pub fn mtime(&self) -> Option<SystemTime> {
+        self.mtime
+    }
+
+    /// Fold a file's modification time into the running max for its directory.
+    fn bump_mtime(&mut self, modified: SystemTime) {
+        self.mtime = Some(match self.mtime {
+            Some(current) => current.max(modified),
+            None => modified,
+        });
+    }
+}
+
+/// Fixed-capacity min-heap of the largest files seen so far, bounded to `capacity` entries.
+///
+/// Pushing costs `O(log capacity)` regardless of how many files are walked, so tracking the
+/// top-N largest files never requires sorting the full file list.
+pub struct TopFiles {
+    capacity: usize,
+    heap: BinaryHeap<Reverse<(u64, PathBuf)>>,
+}
+
+impl TopFiles {
+    pub fn new(capacity: usize) -> Self {
+        TopFiles {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity),
+        }
+    }
+
+    /// Consider `path` (of `size` bytes) for inclusion in the top-N, replacing the current
+    /// smallest entry if the heap is already full and `size` is bigger.
+    fn push(&mut self, size: u64, path: &Path) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.heap.len() < self.capacity {
+            self.heap.push(Reverse((size, path.to_path_buf())));
+        } else if self.heap.peek().is_some_and(|Reverse((min, _))| size > *min) {
+            self.heap.pop();
+            self.heap.push(Reverse((size, path.to_path_buf())));
+        }
+    }
+
+    /// Merge another worker's heap into this one, keeping only the largest `capacity` files.
+    pub fn merge(&mut self, other: TopFiles) {
+        for Reverse((size, path)) in other.heap {
+            self.push(size, &path);
+        }
+    }
+
+    /// Drain the heap into its entries, largest first.
+    pub fn into_sorted_vec(self) -> Vec<(u64, PathBuf)> {
+        let mut files: Vec<(u64, PathBuf)> = self.heap.into_iter().map(|Reverse(f)| f).collect();
+        files.sort_by_key(|(size, _)| Reverse(*size));
+        files
+    }
+}
+
+/// File count and total size per lowercased file extension (or `"<none>"`), populated as each
+/// file is counted during the walk.
+#[derive(Debug, Default)]
+pub struct ExtBreakdown {
+    totals: HashMap<String, (u64, u64)>,
+}
+
+impl ExtBreakdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one file of `size` bytes into its extension's running total.
+    fn push(&mut self, path: &Path, size: u64) {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_else(|| "<none>".to_string());
+        let totals = self.totals.entry(ext).or_insert((0, 0));
+        totals.0 += 1;
+        totals.1 += size;
+    }
+
+    /// Merge another worker's totals into this one.
+    pub fn merge(&mut self, other: ExtBreakdown) {
+        for (ext, (n_files, size)) in other.totals {
+            let totals = self.totals.entry(ext).or_insert((0, 0));
+            totals.0 += n_files;
+            totals.1 += size;
+        }
+    }
+
+    /// Drain the breakdown into `(extension, n_files, size)` triples.
+    pub fn into_entries(self) -> Vec<(String, u64, u64)> {
+        self.totals
+            .into_iter()
+            .map(|(ext, (n_files, size))| (ext, n_files, size))
+            .collect()
+    }
+}
+
+/// Walk `dirpath`, counting files and directories that pass the options' filter, optionally
+/// feeding each file's size into `top` and `by_ext`.
+pub fn walk(
+    dirpath: &str,
+    opts: &WalkOptions,
+    top: Option<&mut TopFiles>,
+    by_ext: Option<&mut ExtBreakdown>,
+) -> io::Result<(PathBuf, Counter)> {
+    let root = Path::new(dirpath).canonicalize()?;
+    let mut counter = Counter::new(dirpath.to_string());
+
+    if opts.gitignore {
+        walk_with_ignore(&root, opts, &mut counter, top, by_ext);
+    } else {
+        walk_dir(&root, opts, &mut counter, top, by_ext)?;
+    }
+
+    Ok((root, counter))
+}
+
+/// Hand-rolled recursive walk used when `--gitignore` is not requested.
+fn walk_dir(
+    dir: &Path,
+    opts: &WalkOptions,
+    counter: &mut Counter,
+    mut top: Option<&mut TopFiles>,
+    mut by_ext: Option<&mut ExtBreakdown>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if !opts.all_files && name.starts_with('.') {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            counter.n_dirs += 1;
+            walk_dir(&path, opts, counter, top.as_deref_mut(), by_ext.as_deref_mut())?;
+        } else {
+            if let Some(re) = &opts.filter {
+                if !re.is_match(&name) {
+                    continue;
+                }
+            }
+            if opts.verbose {
+                println!("{}", path.display());
+            }
+            counter.n_files += 1;
+            if opts.with_size || opts.with_mtime || top.is_some() || by_ext.is_some() {
+                if let Ok(metadata) = entry.metadata() {
+                    let size = file_size(&metadata, opts.disk_usage);
+                    if opts.with_size {
+                        counter.size += size;
+                    }
+                    if opts.with_mtime {
+                        if let Ok(modified) = metadata.modified() {
+                            counter.bump_mtime(modified);
+                        }
+                    }
+                    if let Some(top) = top.as_deref_mut() {
+                        top.push(size, &path);
+                    }
+                    if let Some(by_ext) = by_ext.as_deref_mut() {
+                        by_ext.push(&path, size);
+                    }
+                }
+            }
+            if opts.archives {
+                fold_archive(counter, &path, opts, top.as_deref_mut(), by_ext.as_deref_mut());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// If `--archives` is set and `path` looks like a `.tar`/`.tar.gz`/`.zip` file, open it and fold
+/// the files and directories it contains into `counter`, `top`, and `by_ext`, the same way a
+/// regular file on disk would be. Member sizes are their uncompressed lengths whether or not
+/// `--disk-usage` is set, and member modification times are not tracked.
+fn fold_archive(
+    counter: &mut Counter,
+    path: &Path,
+    opts: &WalkOptions,
+    mut top: Option<&mut TopFiles>,
+    mut by_ext: Option<&mut ExtBreakdown>,
+) {
+    let Some(reader) = archive::open(path) else {
+        return;
+    };
+    let Ok(members) = reader.members() else {
+        return;
+    };
+    for (is_dir, size, name) in members {
+        if is_dir {
+            counter.n_dirs += 1;
+            continue;
+        }
+        let member_path = path.join(&name);
+        if let Some(re) = &opts.filter {
+            let member_name = member_path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            if !re.is_match(&member_name) {
+                continue;
+            }
+        }
+        if opts.verbose {
+            println!("{}", member_path.display());
+        }
+        counter.n_files += 1;
+        if opts.with_size {
+            counter.size += size;
+        }
+        if let Some(top) = top.as_deref_mut() {
+            top.push(size, &member_path);
+        }
+        if let Some(by_ext) = by_ext.as_deref_mut() {
+            by_ext.push(&member_path, size);
+        }
+    }
+}
+
+/// Walk `dir` through the `ignore` crate's single-threaded walker, so `.gitignore`/`.ignore`
+/// files, global git excludes, and the hidden-file convention are honored.
+fn walk_with_ignore(
+    dir: &Path,
+    opts: &WalkOptions,
+    counter: &mut Counter,
+    mut top: Option<&mut TopFiles>,
+    mut by_ext: Option<&mut ExtBreakdown>,
+) {
+    let mut builder = WalkBuilder::new(dir);
+    builder
+        .hidden(!opts.hidden && !opts.all_files)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .ignore(true);
+
+    for result in builder.build() {
+        let Ok(entry) = result else { continue };
+        if entry.path() == dir {
+            continue;
+        }
+        count_entry(opts, counter, &entry, top.as_deref_mut(), by_ext.as_deref_mut());
+    }
+}
+
+fn count_entry(
+    opts: &WalkOptions,
+    counter: &mut Counter,
+    entry: &ignore::DirEntry,
+    mut top: Option<&mut TopFiles>,
+    mut by_ext: Option<&mut ExtBreakdown>,
+) {
+    let Some(file_type) = entry.file_type() else {
+        return;
+    };
+
+    if file_type.is_dir() {
+        counter.n_dirs += 1;
+        return;
+    }
+    if !file_type.is_file() {
+        return;
+    }
+
+    let name = entry.file_name().to_string_lossy();
+    if let Some(re) = &opts.filter {
+        if !re.is_match(&name) {
+            return;
+        }
+    }
+    if opts.verbose {
+        println!("{}", entry.path().display());
+    }
+    counter.n_files += 1;
+    if opts.with_size || opts.with_mtime || top.is_some() || by_ext.is_some() {
+        if let Ok(metadata) = entry.metadata() {
+            let size = file_size(&metadata, opts.disk_usage);
+            if opts.with_size {
+                counter.size += size;
+            }
+            if opts.with_mtime {
+                if let Ok(modified) = metadata.modified() {
+                    counter.bump_mtime(modified);
+                }
+            }
+            if let Some(top) = top.as_deref_mut() {
+                top.push(size, entry.path());
+            }
+            if let Some(by_ext) = by_ext.as_deref_mut() {
+                by_ext.push(entry.path(), size);
+            }
+        }
+    }
+    if opts.archives {
+        fold_archive(counter, entry.path(), opts, top, by_ext);
+    }
+}
+
+/// Walk several directories, one `Counter` per input directory, plus the combined top-N largest
+/// files across all of them when `top_n` is set, and the combined per-extension breakdown when
+/// `by_ext` is set.
+///
+/// Directories are spread across `n_threads` workers. When `opts.gitignore` is set each
+/// directory is instead handed to the `ignore` crate's own parallel walker, which is given
+/// the same `n_threads` budget, so the thread pool is delegated rather than duplicated.
+pub fn parallel_walk(
+    directories: Vec<String>,
+    opts: WalkOptions,
+    n_threads: usize,
+    top_n: Option<usize>,
+    by_ext: bool,
+) -> (Vec<Counter>, Option<TopFiles>, Option<ExtBreakdown>) {
+    let n_threads = n_threads.max(1);
+
+    if opts.gitignore {
+        let mut counters = Vec::with_capacity(directories.len());
+        let mut top = top_n.map(TopFiles::new);
+        let mut by_ext = by_ext.then(ExtBreakdown::new);
+        for dirpath in directories {
+            if let Ok((counter, worker_top, worker_by_ext)) =
+                walk_parallel_with_ignore(&dirpath, &opts, n_threads, top_n, by_ext.is_some())
+            {
+                counters.push(counter);
+                if let (Some(top), Some(worker_top)) = (top.as_mut(), worker_top) {
+                    top.merge(worker_top);
+                }
+                if let (Some(by_ext), Some(worker_by_ext)) = (by_ext.as_mut(), worker_by_ext) {
+                    by_ext.merge(worker_by_ext);
+                }
+            }
+        }
+        return (counters, top, by_ext);
+    }
+
+    let mut slots: Vec<Option<Counter>> = (0..directories.len()).map(|_| None).collect();
+    let mut top = top_n.map(TopFiles::new);
+    let mut by_ext_totals = by_ext.then(ExtBreakdown::new);
+
+    std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for chunk in chunk_indices(directories.len(), n_threads) {
+            let directories = &directories;
+            let opts = &opts;
+            handles.push(scope.spawn(move || {
+                let mut worker_top = top_n.map(TopFiles::new);
+                let mut worker_by_ext = by_ext.then(ExtBreakdown::new);
+                let results = chunk
+                    .into_iter()
+                    .filter_map(|i| {
+                        walk(&directories[i], opts, worker_top.as_mut(), worker_by_ext.as_mut())
+                            .ok()
+                            .map(|(_, counter)| (i, counter))
+                    })
+                    .collect::<Vec<_>>();
+                (results, worker_top, worker_by_ext)
+            }));
+        }
+        for handle in handles {
+            let (results, worker_top, worker_by_ext) = handle.join().expect("walker thread panicked");
+            for (i, counter) in results {
+                slots[i] = Some(counter);
+            }
+            if let (Some(top), Some(worker_top)) = (top.as_mut(), worker_top) {
+                top.merge(worker_top);
+            }
+            if let (Some(by_ext_totals), Some(worker_by_ext)) = (by_ext_totals.as_mut(), worker_by_ext) {
+                by_ext_totals.merge(worker_by_ext);
+            }
+        }
+    });
+
+    (slots.into_iter().flatten().collect(), top, by_ext_totals)
+}
+
+/// Walk `dirpath` using the `ignore` crate's own parallel walker, given `n_threads` workers.
+/// Every entry is folded into a single shared `Counter` and, when requested, a single shared
+/// top-N heap and extension breakdown — all guarded by a `Mutex` since the `ignore` crate's own
+/// threads drive the callback concurrently.
+fn walk_parallel_with_ignore(
+    dirpath: &str,
+    opts: &WalkOptions,
+    n_threads: usize,
+    top_n: Option<usize>,
+    by_ext: bool,
+) -> io::Result<(Counter, Option<TopFiles>, Option<ExtBreakdown>)> {
+    let root = Path::new(dirpath).canonicalize()?;
+    let counter = Mutex::new(Counter::new(dirpath.to_string()));
+    let top = Mutex::new(top_n.map(TopFiles::new));
+    let by_ext = Mutex::new(by_ext.then(ExtBreakdown::new));
+
+    let mut builder = WalkBuilder::new(&root);
+    builder
+        .hidden(!opts.hidden && !opts.all_files)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .ignore(true)
+        .threads(n_threads);
+
+    builder.build_parallel().run(|| {
+        let root = root.clone();
+        let counter = &counter;
+        let top = &top;
+        let by_ext = &by_ext;
+        Box::new(move |result| {
+            if let Ok(entry) = result {
+                if entry.path() != root {
+                    count_entry(
+                        opts,
+                        &mut counter.lock().unwrap(),
+                        &entry,
+                        top.lock().unwrap().as_mut(),
+                        by_ext.lock().unwrap().as_mut(),
+                    );
+                }
+            }
+            WalkState::Continue
+        })
+    });
+
+    Ok((
+        counter.into_inner().unwrap(),
+        top.into_inner().unwrap(),
+        by_ext.into_inner().unwrap(),
+    ))
+}
+
+/// Split `0..len` into up to `n_threads` contiguous chunks.
+fn chunk_indices(len: usize, n_threads: usize) -> Vec<Vec<usize>> {
+    if len == 0 {
+        return Vec::new();
+    }
+    let n_threads = n_threads.min(len);
+    let chunk_size = len.div_ceil(n_threads);
+    (0..len)
+        .collect::<Vec<_>>()
+        .chunks(chunk_size)
+        .map(|c| c.to_vec())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_walk_options() -> WalkOptions {
+        WalkOptions {
+            all_files: false,
+            with_size: false,
+            with_mtime: false,
+            filter: None,
+            verbose: false,
+            gitignore: true,
+            hidden: false,
+            archives: false,
+            disk_usage: false,
+        }
+    }
+
+    #[test]
+    fn gitignore_walk_skips_hidden_files_unless_hidden_or_all_files_is_set() {
+        let dir = std::env::temp_dir().join(format!("fcnt-test-gitignore-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("visible.txt"), b"v").unwrap();
+        fs::write(dir.join(".hidden.txt"), b"h").unwrap();
+        let dirpath = dir.to_str().unwrap();
+
+        let opts = base_walk_options();
+        let (_, counter) = walk(dirpath, &opts, None, None).unwrap();
+        assert_eq!(counter.n_files, 1);
+
+        let mut hidden_opts = opts.clone();
+        hidden_opts.hidden = true;
+        let (_, counter) = walk(dirpath, &hidden_opts, None, None).unwrap();
+        assert_eq!(counter.n_files, 2);
+
+        let mut all_files_opts = opts.clone();
+        all_files_opts.all_files = true;
+        let (_, counter) = walk(dirpath, &all_files_opts, None, None).unwrap();
+        assert_eq!(counter.n_files, 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bump_mtime_keeps_the_latest_modification_time() {
+        let mut counter = Counter::new("d".to_string());
+        assert_eq!(counter.mtime(), None);
+
+        let earlier = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(100);
+        let later = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(200);
+
+        counter.bump_mtime(earlier);
+        assert_eq!(counter.mtime(), Some(earlier));
+
+        counter.bump_mtime(later);
+        assert_eq!(counter.mtime(), Some(later));
+
+        counter.bump_mtime(earlier);
+        assert_eq!(counter.mtime(), Some(later));
+    }
+
+    #[test]
+    fn order_by_t_sorts_most_recent_first_with_none_last() {
+        let mut with_mtime = Counter::new("older".to_string());
+        with_mtime.bump_mtime(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(100));
+
+        let mut with_later_mtime = Counter::new("newer".to_string());
+        with_later_mtime.bump_mtime(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(200));
+
+        let without_mtime = Counter::new("none".to_string());
+
+        let mut counters = [with_mtime, without_mtime, with_later_mtime];
+        counters.sort_by_key(|c| Reverse(c.mtime()));
+
+        let dirpaths: Vec<&str> = counters.iter().map(|c| c.dirpath.as_str()).collect();
+        assert_eq!(dirpaths, vec!["newer", "older", "none"]);
+    }
+
+    #[test]
+    fn top_files_capacity_zero_keeps_nothing() {
+        let mut top = TopFiles::new(0);
+        top.push(100, Path::new("a"));
+        assert!(top.into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn top_files_keeps_the_largest_n_and_evicts_the_smallest() {
+        let mut top = TopFiles::new(2);
+        top.push(10, Path::new("a"));
+        top.push(30, Path::new("b"));
+        top.push(20, Path::new("c"));
+        top.push(5, Path::new("d"));
+
+        let sizes: Vec<u64> = top.into_sorted_vec().into_iter().map(|(size, _)| size).collect();
+        assert_eq!(sizes, vec![30, 20]);
+    }
+
+    #[test]
+    fn top_files_keeps_ties_up_to_capacity() {
+        let mut top = TopFiles::new(2);
+        top.push(10, Path::new("a"));
+        top.push(10, Path::new("b"));
+        top.push(10, Path::new("c"));
+
+        assert_eq!(top.into_sorted_vec().len(), 2);
+    }
+
+    #[test]
+    fn top_files_merge_keeps_the_largest_across_both_heaps() {
+        let mut a = TopFiles::new(2);
+        a.push(5, Path::new("a"));
+        a.push(50, Path::new("b"));
+
+        let mut b = TopFiles::new(2);
+        b.push(40, Path::new("c"));
+        b.push(1, Path::new("d"));
+
+        a.merge(b);
+
+        let sizes: Vec<u64> = a.into_sorted_vec().into_iter().map(|(size, _)| size).collect();
+        assert_eq!(sizes, vec![50, 40]);
+    }
+
+    #[test]
+    fn ext_breakdown_merge_sums_matching_extensions() {
+        let mut a = ExtBreakdown::new();
+        a.push(Path::new("one.txt"), 10);
+
+        let mut b = ExtBreakdown::new();
+        b.push(Path::new("two.txt"), 5);
+        b.push(Path::new("three.md"), 7);
+        b.push(Path::new("no_extension"), 3);
+
+        a.merge(b);
+
+        let mut entries = a.into_entries();
+        entries.sort_by_key(|(ext, _, _)| ext.clone());
+        assert_eq!(
+            entries,
+            vec![
+                ("<none>".to_string(), 1, 3),
+                ("md".to_string(), 1, 7),
+                ("txt".to_string(), 2, 15),
+            ]
+        );
+    }
+
+    #[test]
+    fn fold_archive_leaves_counter_unchanged_on_an_unreadable_archive() {
+        let path = std::env::temp_dir().join(format!("fcnt-test-broken-archive-{}.tar", std::process::id()));
+        fs::write(&path, b"not a tar file").unwrap();
+
+        let opts = WalkOptions {
+            all_files: false,
+            with_size: true,
+            with_mtime: false,
+            filter: None,
+            verbose: false,
+            gitignore: false,
+            hidden: false,
+            archives: true,
+            disk_usage: false,
+        };
+        let mut counter = Counter::new("irrelevant".to_string());
+        fold_archive(&mut counter, &path, &opts, None, None);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(counter.n_files, 0);
+        assert_eq!(counter.n_dirs, 0);
+        assert_eq!(counter.size(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_size_disk_usage_rounds_up_to_the_block_size() {
+        let path = std::env::temp_dir().join(format!("fcnt-test-file-size-{}", std::process::id()));
+        fs::write(&path, [0u8; 1]).unwrap();
+        let metadata = fs::metadata(&path).unwrap();
+
+        let apparent = file_size(&metadata, false);
+        let on_disk = file_size(&metadata, true);
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(apparent, 1);
+        assert_eq!(on_disk % 512, 0);
+        assert!(on_disk >= 512);
+    }
+}